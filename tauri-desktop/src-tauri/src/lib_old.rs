@@ -1,6 +1,74 @@
 // Simplified Tauri commands for Navi
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::http::header::{ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use tauri::http::Response;
+use tauri::{Emitter, Manager};
+use thiserror::Error;
+
+/// Typed failure surface for every command, replacing opaque `String` errors
+/// so the frontend can branch on the `kind` and retry only transient cases.
+/// Serializes to a tagged object, e.g. `{ "kind": "not_found",
+/// "message": "resource not found", "status": 404 }`.
+#[derive(Debug, Error)]
+pub enum NaviError {
+    #[error("network error: {0}")]
+    Network(String),
+    #[error("request timed out")]
+    Timeout,
+    #[error("backend returned status {code}")]
+    HttpStatus { code: u16, body: String },
+    #[error("failed to decode response: {0}")]
+    Decode(String),
+    #[error("unsupported request: {0}")]
+    Unsupported(String),
+    #[error("resource not found")]
+    NotFound,
+}
+
+impl From<reqwest::Error> for NaviError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            NaviError::Timeout
+        } else {
+            NaviError::Network(e.to_string())
+        }
+    }
+}
+
+impl From<serde_json::Error> for NaviError {
+    fn from(e: serde_json::Error) -> Self {
+        NaviError::Decode(e.to_string())
+    }
+}
+
+impl Serialize for NaviError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let (kind, status) = match self {
+            NaviError::Network(_) => ("network", None),
+            NaviError::Timeout => ("timeout", None),
+            NaviError::HttpStatus { code, .. } => ("http_status", Some(*code)),
+            NaviError::Decode(_) => ("decode", None),
+            NaviError::Unsupported(_) => ("unsupported", None),
+            NaviError::NotFound => ("not_found", Some(404)),
+        };
+
+        let mut state = serializer.serialize_struct("NaviError", if status.is_some() { 3 } else { 2 })?;
+        state.serialize_field("kind", kind)?;
+        state.serialize_field("message", &self.to_string())?;
+        if let Some(code) = status {
+            state.serialize_field("status", &code)?;
+        }
+        state.end()
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Persona {
@@ -23,6 +91,51 @@ pub struct Document {
     pub posted_date: Option<String>,
 }
 
+/// Lifecycle state of a [`Comment`]. Serializes to the lowercase wire values
+/// (`"draft"`, `"submitted"`, `"withdrawn"`) the client drives; any other
+/// value coming back from the backend (an unrecognized or backend-only state)
+/// deserializes to [`CommentStatus::Unknown`] rather than failing, so reads
+/// stay as permissive as the stringly-typed baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    Draft,
+    Submitted,
+    Withdrawn,
+    #[serde(other)]
+    Unknown,
+}
+
+impl CommentStatus {
+    /// Whether a comment may move from `self` to `next`. A draft can be
+    /// submitted or withdrawn; a submitted comment can only be withdrawn;
+    /// withdrawn is terminal. Transitions out of an unrecognized state are
+    /// never allowed client-side.
+    fn can_transition_to(self, next: CommentStatus) -> bool {
+        matches!(
+            (self, next),
+            (CommentStatus::Draft, CommentStatus::Submitted)
+                | (CommentStatus::Draft, CommentStatus::Withdrawn)
+                | (CommentStatus::Submitted, CommentStatus::Withdrawn)
+        )
+    }
+}
+
+/// A [`Document`] optionally enriched with related records in a single round
+/// trip, following the `expand` query pattern. The `comments`/`personas`
+/// fields are omitted (and the payload collapses to a bare [`Document`] via
+/// `flatten`) when `expand` is not requested, preserving backward
+/// compatibility with callers that only read document fields.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExpandedDocument {
+    #[serde(flatten)]
+    pub document: Document,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comments: Option<Vec<Comment>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub personas: Option<Vec<Persona>>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Comment {
     pub id: Option<i32>,
@@ -30,111 +143,667 @@ pub struct Comment {
     pub document_id: String,
     pub title: Option<String>,
     pub content: String,
-    pub status: String,
+    pub status: CommentStatus,
+    /// Optimistic-concurrency token echoed back from the backend and sent as
+    /// `If-Match` on updates, so a concurrent write is rejected rather than
+    /// silently clobbered. Absent on freshly created drafts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i64>,
+}
+
+/// Runtime HTTP configuration for talking to the Navi backend.
+///
+/// Loaded from `tauri.conf.json`/environment in [`run`] and kept in Tauri
+/// managed state so the frontend can repoint Navi at a remote deployment and
+/// tune timeouts without rebuilding. The option surface mirrors a typical
+/// Tauri HTTP request API: base url, headered timeouts, redirect controls and
+/// a compression toggle, all applied through [`reqwest::ClientBuilder`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiConfig {
+    pub base_url: String,
+    pub connect_timeout_ms: u64,
+    pub read_timeout_ms: u64,
+    pub timeout_ms: u64,
+    pub follow_redirects: bool,
+    pub max_redirections: usize,
+    pub retry_count: u32,
+    pub retry_backoff_ms: u64,
+    pub gzip: bool,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        ApiConfig {
+            base_url: "http://localhost:8001".to_string(),
+            connect_timeout_ms: 5_000,
+            read_timeout_ms: 30_000,
+            timeout_ms: 60_000,
+            follow_redirects: true,
+            max_redirections: 5,
+            retry_count: 0,
+            retry_backoff_ms: 250,
+            gzip: true,
+        }
+    }
+}
+
+impl ApiConfig {
+    /// Resolve the runtime configuration, layering (lowest precedence first):
+    /// the baked-in [`Default`], the optional `plugins.navi` section of
+    /// `tauri.conf.json`, then `NAVI_API_*` environment variables. Pass the
+    /// JSON section from `app.config()` in `setup`; `None` falls back to
+    /// defaults + env.
+    fn load(conf: Option<&serde_json::Value>) -> Self {
+        let mut config = ApiConfig::default();
+        if let Some(conf) = conf {
+            config.overlay_json(conf);
+        }
+        config.overlay_env();
+        config
+    }
+
+    /// Overlay the `plugins.navi` section of `tauri.conf.json`. Unknown or
+    /// mistyped keys are ignored so a partial section still works.
+    fn overlay_json(&mut self, conf: &serde_json::Value) {
+        if let Some(v) = conf.get("base_url").and_then(|v| v.as_str()) {
+            self.base_url = v.to_string();
+        }
+        if let Some(v) = conf.get("connect_timeout_ms").and_then(|v| v.as_u64()) {
+            self.connect_timeout_ms = v;
+        }
+        if let Some(v) = conf.get("read_timeout_ms").and_then(|v| v.as_u64()) {
+            self.read_timeout_ms = v;
+        }
+        if let Some(v) = conf.get("timeout_ms").and_then(|v| v.as_u64()) {
+            self.timeout_ms = v;
+        }
+        if let Some(v) = conf.get("follow_redirects").and_then(|v| v.as_bool()) {
+            self.follow_redirects = v;
+        }
+        if let Some(v) = conf.get("max_redirections").and_then(|v| v.as_u64()) {
+            self.max_redirections = v as usize;
+        }
+        if let Some(v) = conf.get("retry_count").and_then(|v| v.as_u64()) {
+            self.retry_count = v as u32;
+        }
+        if let Some(v) = conf.get("retry_backoff_ms").and_then(|v| v.as_u64()) {
+            self.retry_backoff_ms = v;
+        }
+        if let Some(v) = conf.get("gzip").and_then(|v| v.as_bool()) {
+            self.gzip = v;
+        }
+    }
+
+    /// Overlay any `NAVI_API_*` environment variables over the full option
+    /// surface, so every field is tunable at load time and not just via
+    /// `set_api_config`.
+    fn overlay_env(&mut self) {
+        if let Ok(base_url) = std::env::var("NAVI_API_BASE_URL") {
+            self.base_url = base_url;
+        }
+        if let Some(v) = env_u64("NAVI_API_CONNECT_TIMEOUT_MS") {
+            self.connect_timeout_ms = v;
+        }
+        if let Some(v) = env_u64("NAVI_API_READ_TIMEOUT_MS") {
+            self.read_timeout_ms = v;
+        }
+        if let Some(v) = env_u64("NAVI_API_TIMEOUT_MS") {
+            self.timeout_ms = v;
+        }
+        if let Some(v) = env_bool("NAVI_API_FOLLOW_REDIRECTS") {
+            self.follow_redirects = v;
+        }
+        if let Some(v) = env_u64("NAVI_API_MAX_REDIRECTIONS") {
+            self.max_redirections = v as usize;
+        }
+        if let Some(v) = env_u64("NAVI_API_RETRY_COUNT") {
+            self.retry_count = v as u32;
+        }
+        if let Some(v) = env_u64("NAVI_API_RETRY_BACKOFF_MS") {
+            self.retry_backoff_ms = v;
+        }
+        if let Some(v) = env_bool("NAVI_API_GZIP") {
+            self.gzip = v;
+        }
+    }
+
+    /// Construct a `reqwest` client from this configuration.
+    fn build_client(&self) -> Result<reqwest::Client, String> {
+        let redirect = if self.follow_redirects {
+            reqwest::redirect::Policy::limited(self.max_redirections)
+        } else {
+            reqwest::redirect::Policy::none()
+        };
+
+        reqwest::Client::builder()
+            .connect_timeout(Duration::from_millis(self.connect_timeout_ms))
+            .read_timeout(Duration::from_millis(self.read_timeout_ms))
+            .timeout(Duration::from_millis(self.timeout_ms))
+            .redirect(redirect)
+            .gzip(self.gzip)
+            .build()
+            .map_err(|e| e.to_string())
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url.trim_end_matches('/'), path)
+    }
+}
+
+fn env_u64(key: &str) -> Option<u64> {
+    std::env::var(key).ok().and_then(|v| v.parse().ok())
+}
+
+fn env_bool(key: &str) -> Option<bool> {
+    match std::env::var(key).ok()?.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Cached backend connectivity, refreshed by the `/health` poller and mirrored
+/// to the frontend through the `api-status-changed` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthStatus {
+    pub online: bool,
+    pub detail: Option<String>,
+}
+
+impl Default for HealthStatus {
+    fn default() -> Self {
+        HealthStatus {
+            online: false,
+            detail: None,
+        }
+    }
+}
+
+/// Shared application state registered with `.manage(...)`.
+///
+/// Holds the single pooled `reqwest::Client`, the resolved [`ApiConfig`] and a
+/// cached [`HealthStatus`], so every command receives one handle instead of
+/// rebuilding a client per call — mirroring the state-backed plugin pattern
+/// where commands are handed an access object.
+pub struct NaviState {
+    client: Mutex<reqwest::Client>,
+    config: Mutex<ApiConfig>,
+    health: Mutex<HealthStatus>,
+}
+
+impl NaviState {
+    fn new(config: ApiConfig) -> Result<Self, String> {
+        let client = config.build_client()?;
+        Ok(NaviState {
+            client: Mutex::new(client),
+            config: Mutex::new(config),
+            health: Mutex::new(HealthStatus::default()),
+        })
+    }
+
+    fn client(&self) -> reqwest::Client {
+        self.client.lock().expect("http client mutex poisoned").clone()
+    }
+
+    fn config(&self) -> ApiConfig {
+        self.config.lock().expect("api config mutex poisoned").clone()
+    }
+
+    /// Swap in a new configuration, rebuilding the pooled client so timeout
+    /// and redirect changes take effect. Shared by `setup` (applying
+    /// `tauri.conf.json`) and `set_api_config`.
+    fn apply_config(&self, config: ApiConfig) -> Result<(), String> {
+        let client = config.build_client()?;
+        *self.client.lock().expect("http client mutex poisoned") = client;
+        *self.config.lock().expect("api config mutex poisoned") = config;
+        Ok(())
+    }
 }
 
 // Simple HTTP client for API calls
-async fn api_request<T>(url: &str, method: &str, body: Option<&str>) -> Result<T, String> 
-where 
-    T: for<'de> Deserialize<'de>
+async fn api_request<T>(state: &NaviState, url: &str, method: &str, body: Option<&str>) -> Result<T, NaviError>
+where
+    T: for<'de> Deserialize<'de>,
 {
-    let client = reqwest::Client::new();
-    let mut request = match method {
-        "GET" => client.get(url),
-        "POST" => client.post(url),
-        "PUT" => client.put(url),
-        _ => return Err("Unsupported HTTP method".to_string()),
-    };
+    api_request_with(state, url, method, body, &[]).await
+}
 
-    if let Some(body_data) = body {
-        request = request.header("Content-Type", "application/json").body(body_data.to_string());
-    }
+/// Like [`api_request`] but attaches extra request headers, used for the
+/// `If-Match` optimistic-concurrency guard on conditional writes.
+async fn api_request_with<T>(
+    state: &NaviState,
+    url: &str,
+    method: &str,
+    body: Option<&str>,
+    headers: &[(&str, String)],
+) -> Result<T, NaviError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let client = state.client();
+    let config = state.config();
+    let mut attempt = 0;
+    loop {
+        let mut request = match method {
+            "GET" => client.get(url),
+            "POST" => client.post(url),
+            "PUT" => client.put(url),
+            "DELETE" => client.delete(url),
+            other => return Err(NaviError::Unsupported(format!("HTTP method {}", other))),
+        };
+
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+
+        if let Some(body_data) = body {
+            request = request
+                .header("Content-Type", "application/json")
+                .body(body_data.to_string());
+        }
 
-    let response = request.send().await.map_err(|e| e.to_string())?;
-    let text = response.text().await.map_err(|e| e.to_string())?;
-    
-    serde_json::from_str(&text).map_err(|e| e.to_string())
+        match request.send().await {
+            Ok(response) => {
+                let status = response.status();
+                if status == reqwest::StatusCode::NOT_FOUND {
+                    return Err(NaviError::NotFound);
+                }
+                if !status.is_success() {
+                    let body = response.text().await.unwrap_or_default();
+                    return Err(NaviError::HttpStatus {
+                        code: status.as_u16(),
+                        body,
+                    });
+                }
+                // A successful DELETE (and other no-content responses) come
+                // back as `204`/empty body; treat that as JSON `null` so the
+                // caller's `T` (e.g. `Value`/`Option<_>`) deserializes cleanly
+                // instead of hitting a misleading EOF decode error.
+                if status == reqwest::StatusCode::NO_CONTENT {
+                    return Ok(serde_json::from_str("null")?);
+                }
+                let text = response.text().await?;
+                if text.trim().is_empty() {
+                    return Ok(serde_json::from_str("null")?);
+                }
+                return Ok(serde_json::from_str(&text)?);
+            }
+            Err(e) => {
+                if attempt >= config.retry_count {
+                    return Err(e.into());
+                }
+                let backoff = config.retry_backoff_ms * (1 << attempt);
+                tokio::time::sleep(Duration::from_millis(backoff)).await;
+                attempt += 1;
+            }
+        }
+    }
 }
 
 // Tauri commands
 #[tauri::command]
-pub async fn get_documents() -> Result<Vec<Document>, String> {
-    api_request::<Vec<Document>>("http://localhost:8001/documents", "GET", None).await
+pub async fn get_documents(state: tauri::State<'_, NaviState>) -> Result<Vec<Document>, NaviError> {
+    let url = state.config().url("/documents");
+    api_request::<Vec<Document>>(&state, &url, "GET", None).await
 }
 
 #[tauri::command]
-pub async fn get_document(document_id: String) -> Result<Document, String> {
-    let url = format!("http://localhost:8001/documents/{}", document_id);
-    api_request::<Document>(&url, "GET", None).await
+pub async fn get_document(
+    state: tauri::State<'_, NaviState>,
+    document_id: String,
+    expand: Option<Vec<String>>,
+) -> Result<ExpandedDocument, NaviError> {
+    let mut path = format!("/documents/{}", document_id);
+    if let Some(expand) = expand.filter(|e| !e.is_empty()) {
+        path.push_str(&format!("?expand={}", expand.join(",")));
+    }
+    let url = state.config().url(&path);
+    api_request::<ExpandedDocument>(&state, &url, "GET", None).await
 }
 
 #[tauri::command]
-pub async fn search_documents(query: String) -> Result<HashMap<String, serde_json::Value>, String> {
-    let url = format!("http://localhost:8001/documents/search?q={}", query);
-    api_request::<HashMap<String, serde_json::Value>>(&url, "GET", None).await
+pub async fn search_documents(
+    state: tauri::State<'_, NaviState>,
+    query: String,
+) -> Result<HashMap<String, serde_json::Value>, NaviError> {
+    let url = state.config().url(&format!("/documents/search?q={}", query));
+    api_request::<HashMap<String, serde_json::Value>>(&state, &url, "GET", None).await
 }
 
 #[tauri::command]
-pub async fn create_persona(name: String, role: Option<String>, interests: Vec<String>) -> Result<Persona, String> {
+pub async fn create_persona(
+    state: tauri::State<'_, NaviState>,
+    name: String,
+    role: Option<String>,
+    interests: Vec<String>,
+) -> Result<Persona, NaviError> {
     let persona = Persona {
         id: None,
         name,
         role,
         interests,
     };
-    
-    let body = serde_json::to_string(&persona).map_err(|e| e.to_string())?;
-    api_request::<Persona>("http://localhost:8001/personas", "POST", Some(&body)).await
+
+    let body = serde_json::to_string(&persona)?;
+    let url = state.config().url("/personas");
+    api_request::<Persona>(&state, &url, "POST", Some(&body)).await
 }
 
 #[tauri::command]
-pub async fn get_persona(persona_id: i32) -> Result<Persona, String> {
-    let url = format!("http://localhost:8001/personas/{}", persona_id);
-    api_request::<Persona>(&url, "GET", None).await
+pub async fn get_persona(
+    state: tauri::State<'_, NaviState>,
+    persona_id: i32,
+) -> Result<Persona, NaviError> {
+    let url = state.config().url(&format!("/personas/{}", persona_id));
+    api_request::<Persona>(&state, &url, "GET", None).await
 }
 
 #[tauri::command]
-pub async fn create_comment(persona_id: i32, document_id: String, title: Option<String>, content: String) -> Result<Comment, String> {
+pub async fn create_comment(
+    state: tauri::State<'_, NaviState>,
+    persona_id: i32,
+    document_id: String,
+    title: Option<String>,
+    content: String,
+) -> Result<Comment, NaviError> {
     let comment = Comment {
         id: None,
         persona_id,
         document_id,
         title,
         content,
-        status: "draft".to_string(),
+        status: CommentStatus::Draft,
+        version: None,
+    };
+
+    let body = serde_json::to_string(&comment)?;
+    let url = state.config().url(&format!("/comments?persona_id={}", persona_id));
+    api_request::<Comment>(&state, &url, "POST", Some(&body)).await
+}
+
+/// Build the `If-Match` header list for a conditional write from the version
+/// the caller last observed, or an empty list when no token is supplied.
+fn if_match(expected_version: Option<i64>) -> Vec<(&'static str, String)> {
+    expected_version
+        .map(|v| vec![("If-Match", v.to_string())])
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub async fn update_comment(
+    state: tauri::State<'_, NaviState>,
+    comment_id: i32,
+    current_status: CommentStatus,
+    title: Option<String>,
+    content: String,
+    status: CommentStatus,
+    expected_version: Option<i64>,
+) -> Result<Comment, NaviError> {
+    if current_status != status && !current_status.can_transition_to(status) {
+        return Err(NaviError::Unsupported(format!(
+            "invalid status transition from {:?} to {:?}",
+            current_status, status
+        )));
+    }
+
+    // Send only the mutable fields; the caller-supplied version rides in
+    // `If-Match` so the backend rejects a write that raced another edit,
+    // rather than issuing a second round trip to re-read the record.
+    let payload = serde_json::json!({
+        "title": title,
+        "content": content,
+        "status": status,
+    });
+    let body = serde_json::to_string(&payload)?;
+    let url = state.config().url(&format!("/comments/{}", comment_id));
+    api_request_with::<Comment>(&state, &url, "PUT", Some(&body), &if_match(expected_version)).await
+}
+
+#[tauri::command]
+pub async fn submit_comment(
+    state: tauri::State<'_, NaviState>,
+    comment_id: i32,
+    current_status: CommentStatus,
+    expected_version: Option<i64>,
+) -> Result<Comment, NaviError> {
+    if !current_status.can_transition_to(CommentStatus::Submitted) {
+        return Err(NaviError::Unsupported(format!(
+            "invalid status transition from {:?} to {:?}",
+            current_status,
+            CommentStatus::Submitted
+        )));
+    }
+
+    let payload = serde_json::json!({ "status": CommentStatus::Submitted });
+    let body = serde_json::to_string(&payload)?;
+    let url = state.config().url(&format!("/comments/{}", comment_id));
+    api_request_with::<Comment>(&state, &url, "PUT", Some(&body), &if_match(expected_version)).await
+}
+
+#[tauri::command]
+pub async fn delete_comment(
+    state: tauri::State<'_, NaviState>,
+    comment_id: i32,
+) -> Result<serde_json::Value, NaviError> {
+    let url = state.config().url(&format!("/comments/{}", comment_id));
+    api_request::<serde_json::Value>(&state, &url, "DELETE", None).await
+}
+
+#[tauri::command]
+pub async fn update_persona(
+    state: tauri::State<'_, NaviState>,
+    persona_id: i32,
+    name: String,
+    role: Option<String>,
+    interests: Vec<String>,
+) -> Result<Persona, NaviError> {
+    let persona = Persona {
+        id: Some(persona_id),
+        name,
+        role,
+        interests,
     };
-    
-    let body = serde_json::to_string(&comment).map_err(|e| e.to_string())?;
-    let url = format!("http://localhost:8001/comments?persona_id={}", persona_id);
-    api_request::<Comment>(&url, "POST", Some(&body)).await
+    let body = serde_json::to_string(&persona)?;
+    let url = state.config().url(&format!("/personas/{}", persona_id));
+    api_request::<Persona>(&state, &url, "PUT", Some(&body)).await
 }
 
 #[tauri::command]
-pub async fn get_comment(comment_id: i32) -> Result<Comment, String> {
-    let url = format!("http://localhost:8001/comments/{}", comment_id);
-    api_request::<Comment>(&url, "GET", None).await
+pub async fn get_comment(
+    state: tauri::State<'_, NaviState>,
+    comment_id: i32,
+) -> Result<Comment, NaviError> {
+    let url = state.config().url(&format!("/comments/{}", comment_id));
+    api_request::<Comment>(&state, &url, "GET", None).await
 }
 
 #[tauri::command]
-pub async fn check_api_health() -> Result<HashMap<String, String>, String> {
-    api_request::<HashMap<String, String>>("http://localhost:8001/health", "GET", None).await
+pub async fn check_api_health(
+    state: tauri::State<'_, NaviState>,
+) -> Result<HashMap<String, String>, NaviError> {
+    let url = state.config().url("/health");
+    api_request::<HashMap<String, String>>(&state, &url, "GET", None).await
 }
 
 #[tauri::command]
-pub async fn bulk_insert_documents(documents: Vec<serde_json::Value>) -> Result<HashMap<String, serde_json::Value>, String> {
+pub async fn bulk_insert_documents(
+    state: tauri::State<'_, NaviState>,
+    documents: Vec<serde_json::Value>,
+) -> Result<HashMap<String, serde_json::Value>, NaviError> {
     let payload = serde_json::json!({
         "documents": documents
     });
-    
-    let body = serde_json::to_string(&payload).map_err(|e| e.to_string())?;
-    api_request::<HashMap<String, serde_json::Value>>("http://localhost:8001/documents/bulk", "POST", Some(&body)).await
+
+    let body = serde_json::to_string(&payload)?;
+    let url = state.config().url("/documents/bulk");
+    api_request::<HashMap<String, serde_json::Value>>(&state, &url, "POST", Some(&body)).await
+}
+
+#[tauri::command]
+pub fn get_api_config(state: tauri::State<'_, NaviState>) -> ApiConfig {
+    state.config()
+}
+
+#[tauri::command]
+pub fn set_api_config(state: tauri::State<'_, NaviState>, config: ApiConfig) -> Result<ApiConfig, NaviError> {
+    state.apply_config(config.clone()).map_err(NaviError::Network)?;
+    Ok(config)
+}
+
+#[tauri::command]
+pub fn get_api_status(state: tauri::State<'_, NaviState>) -> HealthStatus {
+    state.health.lock().expect("health mutex poisoned").clone()
+}
+
+/// Poll `/health` on an interval, updating the cached [`HealthStatus`] and
+/// emitting `api-status-changed`. The first poll is always emitted so the UI
+/// gets an initial state (including an offline backend at startup); later
+/// polls emit only when the online flag flips, giving a single source of
+/// truth for connectivity.
+async fn poll_health(app: tauri::AppHandle) {
+    let mut first = true;
+    loop {
+        let state = app.state::<NaviState>();
+        let client = state.client();
+        let url = state.config().url("/health");
+
+        let status = match client.get(&url).send().await {
+            Ok(response) if response.status().is_success() => HealthStatus {
+                online: true,
+                detail: None,
+            },
+            Ok(response) => HealthStatus {
+                online: false,
+                detail: Some(format!("status {}", response.status().as_u16())),
+            },
+            Err(e) => HealthStatus {
+                online: false,
+                detail: Some(e.to_string()),
+            },
+        };
+
+        let changed = {
+            let mut cached = state.health.lock().expect("health mutex poisoned");
+            let changed = cached.online != status.online;
+            *cached = status.clone();
+            changed
+        };
+        if changed || first {
+            let _ = app.emit("api-status-changed", &status);
+        }
+        first = false;
+
+        tokio::time::sleep(Duration::from_secs(15)).await;
+    }
+}
+
+fn error_response(code: u16, message: &str) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(code)
+        .header(CONTENT_TYPE, "text/plain")
+        .body(message.as_bytes().to_vec())
+        .expect("failed to build error response")
+}
+
+/// Resolve a `navi://` URI by proxying the backend, forwarding an optional
+/// `Range` so the webview can seek large documents/attachments without
+/// re-downloading them.
+///
+/// `navi://document/{document_id}` serves the document body and
+/// `navi://attachment/{document_id}` the associated attachment. The incoming
+/// `Range` header is forwarded verbatim and the backend's response — including
+/// a `206 Partial Content` with its `Content-Range`/`Content-Length` — is
+/// passed straight through, so only the requested slice ever crosses IPC.
+async fn fetch_navi_resource(
+    app: &tauri::AppHandle,
+    uri: &tauri::http::Uri,
+    range: Option<&str>,
+) -> Response<Vec<u8>> {
+    let state = app.state::<NaviState>();
+    let client = state.client();
+    let config = state.config();
+
+    let id = uri.path().trim_start_matches('/');
+    let backend_path = match uri.host() {
+        Some("document") => format!("/documents/{}/content", id),
+        Some("attachment") => format!("/documents/{}/attachment", id),
+        _ => return error_response(404, "unknown navi resource"),
+    };
+
+    let mut request = client.get(config.url(&backend_path));
+    if let Some(range) = range {
+        request = request.header(RANGE, range);
+    }
+
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => return error_response(502, &e.to_string()),
+    };
+
+    // Proxy the backend's status (200/206/416/4xx) and the headers the webview
+    // needs to render and seek, advertising range support on every response.
+    let mut builder = Response::builder()
+        .status(response.status().as_u16())
+        .header(ACCEPT_RANGES, "bytes");
+    let mut has_content_type = false;
+    for name in [CONTENT_TYPE, CONTENT_RANGE, CONTENT_LENGTH] {
+        if let Some(value) = response.headers().get(&name).and_then(|v| v.to_str().ok()) {
+            if name == CONTENT_TYPE {
+                has_content_type = true;
+            }
+            builder = builder.header(name, value.to_string());
+        }
+    }
+    if !has_content_type {
+        builder = builder.header(CONTENT_TYPE, "application/octet-stream");
+    }
+
+    let bytes = match response.bytes().await {
+        Ok(bytes) => bytes.to_vec(),
+        Err(e) => return error_response(502, &e.to_string()),
+    };
+
+    builder
+        .body(bytes)
+        .unwrap_or_else(|_| error_response(502, "failed to build navi response"))
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Seed with defaults + env; the `tauri.conf.json` overlay is applied in
+    // `setup` once the resolved app config is available.
+    let state = NaviState::new(ApiConfig::load(None)).expect("failed to build HTTP client");
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_http::init())
+        .manage(state)
+        .register_asynchronous_uri_scheme_protocol("navi", |ctx, request, responder| {
+            let app = ctx.app_handle().clone();
+            let uri = request.uri().clone();
+            let range = request
+                .headers()
+                .get(RANGE)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string());
+            tauri::async_runtime::spawn(async move {
+                let response = fetch_navi_resource(&app, &uri, range.as_deref()).await;
+                responder.respond(response);
+            });
+        })
+        .setup(|app| {
+            // Overlay the `plugins.navi` section of `tauri.conf.json` (then env)
+            // onto the seeded config, so Navi can be repointed without a rebuild.
+            let conf = app.config().plugins.0.get("navi").cloned();
+            let resolved = ApiConfig::load(conf.as_ref());
+            if let Err(e) = app.state::<NaviState>().apply_config(resolved) {
+                eprintln!("failed to apply HTTP config from tauri.conf.json: {}", e);
+            }
+
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(poll_health(handle));
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             get_documents,
             get_document,
@@ -143,9 +812,16 @@ pub fn run() {
             get_persona,
             create_comment,
             get_comment,
+            update_comment,
+            submit_comment,
+            delete_comment,
+            update_persona,
             check_api_health,
-            bulk_insert_documents
+            bulk_insert_documents,
+            get_api_config,
+            set_api_config,
+            get_api_status
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
-}
\ No newline at end of file
+}